@@ -11,6 +11,13 @@ use std::{
     task::{RawWaker, RawWakerVTable},
 };
 
+#[macro_use]
+#[doc(hidden)]
+pub mod macros;
+
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 /// Future for the [`poll_fn`] function.
 pub struct PollFn<F> {
     f: F,
@@ -40,6 +47,25 @@ pub fn run<F: Future>(future: F) -> F::Output {
     BasicScheduler.block_on(future)
 }
 
+/// Yields execution back to the scheduler once.
+///
+/// The task wakes itself before returning `Pending`, so it is polled again
+/// on the next pass.
+pub async fn yield_now() {
+    let mut yielded = false;
+
+    poll_fn(|cx| {
+        if yielded {
+            return Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Pending
+    })
+    .await
+}
+
 pub(crate) struct BasicScheduler;
 
 impl BasicScheduler {
@@ -0,0 +1,56 @@
+/// Asserts that a `Poll` is ready, returning the value.
+#[macro_export]
+macro_rules! assert_ready {
+    ($e:expr) => {{
+        match $e {
+            $crate::macros::support::Poll::Ready(v) => v,
+            $crate::macros::support::Poll::Pending => panic!("pending"),
+        }
+    }};
+    ($e:expr, $($msg:tt)+) => {{
+        match $e {
+            $crate::macros::support::Poll::Ready(v) => v,
+            $crate::macros::support::Poll::Pending => {
+                panic!("pending; {}", format_args!($($msg)+))
+            }
+        }
+    }};
+}
+
+/// Asserts that a `Poll<Result<..>>` is ready and `Ok`, returning the value.
+#[macro_export]
+macro_rules! assert_ready_ok {
+    ($e:expr) => {{
+        match $crate::assert_ready!($e) {
+            Ok(v) => v,
+            Err(e) => panic!("error = {:?}", e),
+        }
+    }};
+    ($e:expr, $($msg:tt)+) => {{
+        match $crate::assert_ready!($e, $($msg)+) {
+            Ok(v) => v,
+            Err(e) => panic!("error = {:?}; {}", e, format_args!($($msg)+)),
+        }
+    }};
+}
+
+/// Asserts that a `Poll` is pending.
+#[macro_export]
+macro_rules! assert_pending {
+    ($e:expr) => {{
+        match $e {
+            $crate::macros::support::Poll::Pending => {}
+            $crate::macros::support::Poll::Ready(v) => {
+                panic!("ready; value = {:?}", v);
+            }
+        }
+    }};
+    ($e:expr, $($msg:tt)+) => {{
+        match $e {
+            $crate::macros::support::Poll::Pending => {}
+            $crate::macros::support::Poll::Ready(v) => {
+                panic!("ready; value = {:?}; {}", v, format_args!($($msg)+));
+            }
+        }
+    }};
+}
@@ -0,0 +1,12 @@
+#[cfg(feature = "test-util")]
+#[macro_use]
+mod assert;
+
+#[macro_use]
+mod pin;
+
+#[macro_use]
+mod select;
+
+#[doc(hidden)]
+pub mod support;
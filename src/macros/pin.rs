@@ -0,0 +1,57 @@
+/// Pins a value on the stack.
+///
+/// Futures that are not `Unpin` have to be pinned before they can be polled
+/// by reference, for example to keep polling the same future across the
+/// iterations of a `select!` loop. `pin!` shadows each given variable with a
+/// `Pin<&mut T>` to the original value, which can no longer be moved.
+///
+/// The `let` form declares and pins in a single step.
+///
+/// # Examples
+///
+/// ```
+/// use llvm_error::{pin, select};
+///
+/// async fn ready(v: u32) -> u32 {
+///     v
+/// }
+///
+/// let out = llvm_error::run(async {
+///     let fut = ready(1);
+///     pin!(fut);
+///
+///     pin! {
+///         let other = ready(2);
+///     }
+///
+///     select! {
+///         biased;
+///
+///         v = &mut fut => v,
+///         v = &mut other => v,
+///     }
+/// });
+///
+/// assert_eq!(out, 1);
+/// ```
+#[macro_export]
+macro_rules! pin {
+    ($($x:ident),* $(,)?) => { $(
+        // Move the value to ensure that it is owned
+        let mut $x = $x;
+        // Shadow the original binding so that it can't be directly accessed
+        // ever again.
+        #[allow(unused_mut)]
+        let mut $x = unsafe {
+            $crate::macros::support::Pin::new_unchecked(&mut $x)
+        };
+    )* };
+    ($(
+        let $x:ident = $init:expr;
+    )*) => {
+        $(
+            let $x = $init;
+            $crate::pin!($x);
+        )*
+    };
+}
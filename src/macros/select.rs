@@ -0,0 +1,212 @@
+/// Waits on multiple concurrent branches, returning when the **first** branch
+/// completes and dropping the remaining ones.
+///
+/// Branches have the form:
+///
+/// ```text
+/// <pattern> = <async expression> (, if <precondition>)? => <handler>,
+/// ```
+///
+/// Every precondition is evaluated first and branches whose precondition is
+/// `false` are disabled. All async expressions are then evaluated, and the
+/// futures of the enabled branches are polled concurrently. When one of them
+/// completes, its output is matched against `<pattern>`: on a match the
+/// `<handler>` runs and becomes the value of the `select!`, otherwise the
+/// branch is disabled and the remaining branches keep being polled. If every
+/// branch ends up disabled, the optional trailing `else => <expression>`
+/// branch is evaluated; without one, `select!` panics.
+///
+/// By default the first branch to poll is picked at random on every call so
+/// that no branch is starved. Starting the macro with `biased;` polls the
+/// branches in the order they are written instead.
+///
+/// The pattern is checked against a reference to the output before the
+/// output is moved into the handler, so refutable patterns must not use
+/// `mut` bindings on non-`Copy` values.
+///
+/// # Examples
+///
+/// ```
+/// async fn ready(v: u32) -> u32 {
+///     v
+/// }
+///
+/// let out = llvm_error::run(async {
+///     llvm_error::select! {
+///         biased;
+///
+///         v = ready(1), if false => v,
+///         v = ready(2) => v * 10,
+///         else => 0,
+///     }
+/// });
+///
+/// assert_eq!(out, 20);
+/// ```
+#[macro_export]
+macro_rules! select {
+    // All branches have been normalized, generate the poll loop.
+    (@expand {
+        biased = $biased:tt;
+        ( $( ($v:ident $i:tt) [$bind:pat] [$fut:expr] [$c:expr] [$h:expr] )+ )
+        else = [$else:expr];
+    }) => {{
+        mod __select_util {
+            #[allow(non_camel_case_types)]
+            pub(super) enum Out<$($v),+> {
+                $( $v($v), )+
+                Disabled,
+            }
+        }
+
+        let branches: u32 = [$($i),+].len() as u32;
+        let mut disabled: u32 = 0;
+
+        $(
+            if !$c {
+                disabled |= 1 << $i;
+            }
+        )+
+
+        let output = {
+            // Safety: nothing may be moved out of `futures`. This upholds the
+            // contract of the `Pin::new_unchecked` calls below.
+            let mut futures = ( $( $fut, )+ );
+            let futures = &mut futures;
+
+            $crate::macros::support::poll_fn(|cx| {
+                let mut is_pending = false;
+
+                let start = if $biased {
+                    0
+                } else {
+                    $crate::macros::support::thread_rng_n(branches)
+                };
+
+                for i in 0..branches {
+                    let branch = (start + i) % branches;
+
+                    match branch {
+                        $(
+                            $i => {
+                                if disabled & (1 << $i) != 0 {
+                                    continue;
+                                }
+
+                                let fut = unsafe {
+                                    $crate::macros::support::Pin::new_unchecked(&mut futures.$i)
+                                };
+
+                                let out = match $crate::macros::support::Future::poll(fut, cx) {
+                                    $crate::macros::support::Poll::Ready(out) => out,
+                                    $crate::macros::support::Poll::Pending => {
+                                        is_pending = true;
+                                        continue;
+                                    }
+                                };
+
+                                // The future has completed and must not be
+                                // polled again.
+                                disabled |= 1 << $i;
+
+                                #[allow(unused_variables, unused_mut)]
+                                match &out {
+                                    $bind => {}
+                                    #[allow(unreachable_patterns)]
+                                    _ => continue,
+                                }
+
+                                return $crate::macros::support::Poll::Ready(
+                                    __select_util::Out::$v(out),
+                                );
+                            }
+                        )+
+                        _ => unreachable!("select! branch index out of range"),
+                    }
+                }
+
+                if is_pending {
+                    $crate::macros::support::Poll::Pending
+                } else {
+                    $crate::macros::support::Poll::Ready(__select_util::Out::Disabled)
+                }
+            })
+            .await
+        };
+
+        match output {
+            $( __select_util::Out::$v($bind) => $h, )+
+            __select_util::Out::Disabled => $else,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("failed to match bind"),
+        }
+    }};
+
+    // ===== normalize branches =====
+
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ $($supply:tt)* ] } else => $else:expr $(,)?) => {
+        $crate::select!(@expand { biased = $biased; ( $($b)* ) else = [$else]; })
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ $($supply:tt)* ] }) => {
+        $crate::select!(@expand {
+            biased = $biased;
+            ( $($b)* )
+            else = [panic!("all select! branches are disabled and there is no else branch")];
+        })
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [] } $($t:tt)+) => {
+        compile_error!("select! supports at most 32 branches")
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr, if $c:expr => $h:block, $($r:tt)*
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [$c] [$h] ) [ $($supply)* ] } $($r)*)
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr => $h:block, $($r:tt)*
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [true] [$h] ) [ $($supply)* ] } $($r)*)
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr, if $c:expr => $h:block $($r:tt)*
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [$c] [$h] ) [ $($supply)* ] } $($r)*)
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr => $h:block $($r:tt)*
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [true] [$h] ) [ $($supply)* ] } $($r)*)
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr, if $c:expr => $h:expr $(, $($r:tt)*)?
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [$c] [$h] ) [ $($supply)* ] } $($($r)*)?)
+    };
+    (@ { biased = $biased:tt; ( $($b:tt)* ) [ ($v:ident $i:tt) $($supply:tt)* ] }
+        $p:pat = $f:expr => $h:expr $(, $($r:tt)*)?
+    ) => {
+        $crate::select!(@ { biased = $biased; ( $($b)* ($v $i) [$p] [$f] [true] [$h] ) [ $($supply)* ] } $($($r)*)?)
+    };
+    (@ $($t:tt)*) => {
+        compile_error!("select! expects `<pattern> = <future> (, if <precondition>)? => <handler>` branches, at least one of them")
+    };
+
+    // ===== entry points =====
+
+    (biased; $($t:tt)*) => {
+        $crate::select!(@ { biased = true; () [
+            (_0 0) (_1 1) (_2 2) (_3 3) (_4 4) (_5 5) (_6 6) (_7 7)
+            (_8 8) (_9 9) (_10 10) (_11 11) (_12 12) (_13 13) (_14 14) (_15 15)
+            (_16 16) (_17 17) (_18 18) (_19 19) (_20 20) (_21 21) (_22 22) (_23 23)
+            (_24 24) (_25 25) (_26 26) (_27 27) (_28 28) (_29 29) (_30 30) (_31 31)
+        ] } $($t)*)
+    };
+    ($($t:tt)*) => {
+        $crate::select!(@ { biased = false; () [
+            (_0 0) (_1 1) (_2 2) (_3 3) (_4 4) (_5 5) (_6 6) (_7 7)
+            (_8 8) (_9 9) (_10 10) (_11 11) (_12 12) (_13 13) (_14 14) (_15 15)
+            (_16 16) (_17 17) (_18 18) (_19 19) (_20 20) (_21 21) (_22 22) (_23 23)
+            (_24 24) (_25 25) (_26 26) (_27 27) (_28 28) (_29 29) (_30 30) (_31 31)
+        ] } $($t)*)
+    };
+}
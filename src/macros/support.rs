@@ -0,0 +1,26 @@
+pub use crate::poll_fn;
+
+pub use std::future::Future;
+pub use std::pin::Pin;
+pub use std::task::Poll;
+
+use std::cell::Cell;
+
+thread_local! {
+    static RNG: Cell<u32> = const { Cell::new(0x9E37_79B9) };
+}
+
+/// Returns a pseudo-random number in `0..n`, used to pick the first branch
+/// polled by an unbiased `select!`.
+pub fn thread_rng_n(n: u32) -> u32 {
+    RNG.with(|rng| {
+        // xorshift32
+        let mut s = rng.get();
+        s ^= s << 13;
+        s ^= s >> 17;
+        s ^= s << 5;
+        rng.set(s);
+
+        ((u64::from(s) * u64::from(n)) >> 32) as u32
+    })
+}
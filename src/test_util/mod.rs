@@ -0,0 +1,5 @@
+//! Utilities for testing futures against this crate's primitives.
+//!
+//! Only available with the `test-util` feature enabled.
+
+pub mod task;
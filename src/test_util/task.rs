@@ -0,0 +1,180 @@
+//! Mock task harness for polling futures by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use llvm_error::test_util::task;
+//! use llvm_error::{assert_pending, assert_ready};
+//! use std::task::Poll;
+//!
+//! let mut rx = llvm_error::unbounded_channel::<()>();
+//! let mut recv = task::spawn(rx.recv());
+//!
+//! assert_pending!(recv.poll());
+//! assert!(!recv.is_woken());
+//!
+//! let mut polled = false;
+//! let mut wake_once = task::spawn(llvm_error::poll_fn(|cx| {
+//!     if polled {
+//!         return Poll::Ready(());
+//!     }
+//!     polled = true;
+//!     cx.waker().wake_by_ref();
+//!     Poll::Pending
+//! }));
+//!
+//! assert_pending!(wake_once.poll());
+//! assert!(wake_once.is_woken());
+//! assert_ready!(wake_once.poll());
+//! ```
+
+use std::future::Future;
+use std::ops;
+use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// Spawns a future into a [`Spawn`] harness so it can be polled by hand.
+pub fn spawn<T>(task: T) -> Spawn<T> {
+    Spawn {
+        task: MockTask::new(),
+        future: Box::pin(task),
+    }
+}
+
+/// Future wrapped by a mock task, created by [`spawn`].
+#[derive(Debug)]
+pub struct Spawn<T> {
+    task: MockTask,
+    future: Pin<Box<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct MockTask {
+    waker: Arc<ThreadWaker>,
+}
+
+#[derive(Debug)]
+struct ThreadWaker {
+    woken: AtomicBool,
+}
+
+// ===== impl Spawn =====
+
+impl<T> Spawn<T> {
+    /// Consumes `self`, returning the inner future.
+    pub fn into_inner(self) -> T
+    where
+        T: Unpin,
+    {
+        *Pin::into_inner(self.future)
+    }
+
+    /// Returns `true` if the task was woken since the last call to `poll`.
+    pub fn is_woken(&self) -> bool {
+        self.task.is_woken()
+    }
+
+    /// Returns the number of wakers that currently point to the task.
+    pub fn waker_ref_count(&self) -> usize {
+        self.task.waker_ref_count()
+    }
+
+    /// Runs `f` with a context backed by the mock task and the pinned future.
+    pub fn enter<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Context<'_>, Pin<&mut T>) -> R,
+    {
+        let fut = self.future.as_mut();
+        self.task.enter(|cx| f(cx, fut))
+    }
+}
+
+impl<T: Future> Spawn<T> {
+    /// Polls the future once, clearing the woken flag first.
+    pub fn poll(&mut self) -> Poll<T::Output> {
+        let fut = self.future.as_mut();
+        self.task.enter(|cx| fut.poll(cx))
+    }
+}
+
+impl<T: Unpin> ops::Deref for Spawn<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.future
+    }
+}
+
+impl<T: Unpin> ops::DerefMut for Spawn<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.future
+    }
+}
+
+// ===== impl MockTask =====
+
+impl MockTask {
+    fn new() -> MockTask {
+        MockTask {
+            waker: Arc::new(ThreadWaker {
+                woken: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    fn enter<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Context<'_>) -> R,
+    {
+        self.waker.woken.store(false, SeqCst);
+
+        let waker = self.waker();
+        let mut cx = Context::from_waker(&waker);
+
+        f(&mut cx)
+    }
+
+    fn is_woken(&self) -> bool {
+        self.waker.woken.load(SeqCst)
+    }
+
+    fn waker_ref_count(&self) -> usize {
+        Arc::strong_count(&self.waker) - 1
+    }
+
+    fn waker(&self) -> Waker {
+        let raw = Arc::into_raw(self.waker.clone()) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(raw, waker_vtable())) }
+    }
+}
+
+fn waker_vtable() -> &'static RawWakerVTable {
+    &RawWakerVTable::new(
+        clone_arc_raw,
+        wake_arc_raw,
+        wake_by_ref_arc_raw,
+        drop_arc_raw,
+    )
+}
+
+unsafe fn clone_arc_raw(data: *const ()) -> RawWaker {
+    Arc::increment_strong_count(data as *const ThreadWaker);
+    RawWaker::new(data, waker_vtable())
+}
+
+unsafe fn wake_arc_raw(data: *const ()) {
+    let waker = Arc::from_raw(data as *const ThreadWaker);
+    waker.woken.store(true, SeqCst);
+}
+
+unsafe fn wake_by_ref_arc_raw(data: *const ()) {
+    let waker = &*(data as *const ThreadWaker);
+    waker.woken.store(true, SeqCst);
+}
+
+unsafe fn drop_arc_raw(data: *const ()) {
+    drop(Arc::from_raw(data as *const ThreadWaker));
+}